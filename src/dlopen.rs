@@ -0,0 +1,377 @@
+//! Runtime loading of `libsystemd.so.0` for the `open` cargo feature.
+//!
+//! When the `open` feature is enabled, [`JournalReader`](crate::reader::JournalReader)
+//! and the other journal APIs no longer link against `libsystemd` at build
+//! time. Instead, the first call into the journal resolves `libsystemd.so.0`
+//! and the handful of `sd_journal_*` symbols it needs via `dlopen`/`dlsym`
+//! (through the `libloading` crate), so a single binary can run with or
+//! without systemd present: on a host missing the library, journal calls
+//! simply return an `io::Error` of kind `Unsupported` instead of the process
+//! failing to start at link time.
+
+use std::ffi::c_void;
+use std::io;
+use std::sync::OnceLock;
+
+use libc::{c_char, c_int, size_t};
+use libloading::Library;
+
+use crate::{const_iovec, sd_id128_t};
+
+const LIB_NAME: &str = "libsystemd.so.0";
+
+/// The subset of `libsystemd`'s `sd_journal_*` symbols this crate needs,
+/// resolved lazily and cached for the lifetime of the process.
+pub(crate) struct SystemdApi {
+	// Kept alive for as long as any resolved symbol may still be called.
+	_lib: Library,
+
+	sd_journal_open: unsafe extern "C" fn(*mut *mut c_void, c_int) -> c_int,
+	sd_journal_open_namespace: unsafe extern "C" fn(*mut *mut c_void, *const c_char, c_int) -> c_int,
+	sd_journal_close: unsafe extern "C" fn(*mut c_void),
+	sd_journal_next: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_previous: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_seek_head: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_seek_tail: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_seek_cursor: unsafe extern "C" fn(*mut c_void, *const c_char) -> c_int,
+	sd_journal_seek_realtime_usec: unsafe extern "C" fn(*mut c_void, u64) -> c_int,
+	sd_journal_seek_monotonic_usec: unsafe extern "C" fn(*mut c_void, sd_id128_t, u64) -> c_int,
+	sd_journal_get_cursor: unsafe extern "C" fn(*mut c_void, *mut *mut c_char) -> c_int,
+	sd_journal_restart_data: unsafe extern "C" fn(*mut c_void),
+	sd_journal_enumerate_data:
+		unsafe extern "C" fn(*mut c_void, *mut *const u8, *mut size_t) -> c_int,
+	sd_journal_get_realtime_usec: unsafe extern "C" fn(*mut c_void, *mut u64) -> c_int,
+	sd_journal_get_monotonic_usec:
+		unsafe extern "C" fn(*mut c_void, *mut u64, *mut sd_id128_t) -> c_int,
+	sd_journal_wait: unsafe extern "C" fn(*mut c_void, u64) -> c_int,
+	sd_journal_add_match: unsafe extern "C" fn(*mut c_void, *const c_void, size_t) -> c_int,
+	sd_journal_add_disjunction: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_add_conjunction: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_flush_matches: unsafe extern "C" fn(*mut c_void),
+	sd_journal_query_unique: unsafe extern "C" fn(*mut c_void, *const c_char) -> c_int,
+	sd_journal_restart_unique: unsafe extern "C" fn(*mut c_void),
+	sd_journal_enumerate_unique:
+		unsafe extern "C" fn(*mut c_void, *mut *const u8, *mut size_t) -> c_int,
+	sd_journal_get_fd: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_get_events: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_get_timeout: unsafe extern "C" fn(*mut c_void, *mut u64) -> c_int,
+	sd_journal_process: unsafe extern "C" fn(*mut c_void) -> c_int,
+	sd_journal_get_cutoff_realtime_usec:
+		unsafe extern "C" fn(*mut c_void, *mut u64, *mut u64) -> c_int,
+	sd_journal_get_cutoff_monotonic_usec:
+		unsafe extern "C" fn(*mut c_void, sd_id128_t, *mut u64, *mut u64) -> c_int,
+	sd_journal_next_skip: unsafe extern "C" fn(*mut c_void, u64) -> c_int,
+	sd_journal_previous_skip: unsafe extern "C" fn(*mut c_void, u64) -> c_int,
+	sd_journal_get_catalog: unsafe extern "C" fn(*mut c_void, *mut *mut c_char) -> c_int,
+	sd_journal_sendv: unsafe extern "C" fn(*const const_iovec, c_int) -> c_int,
+	sd_id128_get_boot: unsafe extern "C" fn(*mut sd_id128_t) -> c_int,
+}
+
+/// Resolve a single symbol by its C name, byte-for-byte.
+fn load_symbol<T: Copy>(lib: &Library, name: &str) -> io::Result<T> {
+	let mut nul_terminated = name.as_bytes().to_vec();
+	nul_terminated.push(0);
+
+	unsafe { lib.get::<T>(&nul_terminated) }
+		.map(|sym| *sym)
+		.map_err(|e| io::Error::new(io::ErrorKind::Unsupported, e.to_string()))
+}
+
+macro_rules! load_symbols {
+	($lib:expr, { $($field:ident),* $(,)? }) => {
+		$(let $field = load_symbol($lib, stringify!($field))?;)*
+	};
+}
+
+impl SystemdApi {
+	fn load() -> io::Result<Self> {
+		let lib = unsafe { Library::new(LIB_NAME) }
+			.map_err(|e| io::Error::new(io::ErrorKind::Unsupported, e.to_string()))?;
+
+		load_symbols!(&lib, {
+			sd_journal_open,
+			sd_journal_open_namespace,
+			sd_journal_close,
+			sd_journal_next,
+			sd_journal_previous,
+			sd_journal_seek_head,
+			sd_journal_seek_tail,
+			sd_journal_seek_cursor,
+			sd_journal_seek_realtime_usec,
+			sd_journal_seek_monotonic_usec,
+			sd_journal_get_cursor,
+			sd_journal_restart_data,
+			sd_journal_enumerate_data,
+			sd_journal_get_realtime_usec,
+			sd_journal_get_monotonic_usec,
+			sd_journal_wait,
+			sd_journal_add_match,
+			sd_journal_add_disjunction,
+			sd_journal_add_conjunction,
+			sd_journal_flush_matches,
+			sd_journal_query_unique,
+			sd_journal_restart_unique,
+			sd_journal_enumerate_unique,
+			sd_journal_get_fd,
+			sd_journal_get_events,
+			sd_journal_get_timeout,
+			sd_journal_process,
+			sd_journal_get_cutoff_realtime_usec,
+			sd_journal_get_cutoff_monotonic_usec,
+			sd_journal_next_skip,
+			sd_journal_previous_skip,
+			sd_journal_get_catalog,
+			sd_journal_sendv,
+			sd_id128_get_boot,
+		});
+
+		Ok(Self {
+			sd_journal_open,
+			sd_journal_open_namespace,
+			sd_journal_close,
+			sd_journal_next,
+			sd_journal_previous,
+			sd_journal_seek_head,
+			sd_journal_seek_tail,
+			sd_journal_seek_cursor,
+			sd_journal_seek_realtime_usec,
+			sd_journal_seek_monotonic_usec,
+			sd_journal_get_cursor,
+			sd_journal_restart_data,
+			sd_journal_enumerate_data,
+			sd_journal_get_realtime_usec,
+			sd_journal_get_monotonic_usec,
+			sd_journal_wait,
+			sd_journal_add_match,
+			sd_journal_add_disjunction,
+			sd_journal_add_conjunction,
+			sd_journal_flush_matches,
+			sd_journal_query_unique,
+			sd_journal_restart_unique,
+			sd_journal_enumerate_unique,
+			sd_journal_get_fd,
+			sd_journal_get_events,
+			sd_journal_get_timeout,
+			sd_journal_process,
+			sd_journal_get_cutoff_realtime_usec,
+			sd_journal_get_cutoff_monotonic_usec,
+			sd_journal_next_skip,
+			sd_journal_previous_skip,
+			sd_journal_get_catalog,
+			sd_journal_sendv,
+			sd_id128_get_boot,
+			_lib: lib,
+		})
+	}
+
+	pub(crate) unsafe fn sd_journal_open(&self, ret: *mut *mut c_void, flags: c_int) -> c_int {
+		(self.sd_journal_open)(ret, flags)
+	}
+
+	pub(crate) unsafe fn sd_journal_open_namespace(
+		&self,
+		ret: *mut *mut c_void,
+		namespace: *const c_char,
+		flags: c_int,
+	) -> c_int {
+		(self.sd_journal_open_namespace)(ret, namespace, flags)
+	}
+
+	pub(crate) unsafe fn sd_journal_close(&self, j: *mut c_void) {
+		(self.sd_journal_close)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_next(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_next)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_previous(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_previous)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_seek_head(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_seek_head)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_seek_tail(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_seek_tail)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_seek_cursor(
+		&self,
+		j: *mut c_void,
+		cursor: *const c_char,
+	) -> c_int {
+		(self.sd_journal_seek_cursor)(j, cursor)
+	}
+
+	pub(crate) unsafe fn sd_journal_seek_realtime_usec(&self, j: *mut c_void, usec: u64) -> c_int {
+		(self.sd_journal_seek_realtime_usec)(j, usec)
+	}
+
+	pub(crate) unsafe fn sd_journal_seek_monotonic_usec(
+		&self,
+		j: *mut c_void,
+		boot_id: sd_id128_t,
+		usec: u64,
+	) -> c_int {
+		(self.sd_journal_seek_monotonic_usec)(j, boot_id, usec)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_cursor(
+		&self,
+		j: *mut c_void,
+		cursor: *mut *mut c_char,
+	) -> c_int {
+		(self.sd_journal_get_cursor)(j, cursor)
+	}
+
+	pub(crate) unsafe fn sd_journal_restart_data(&self, j: *mut c_void) {
+		(self.sd_journal_restart_data)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_enumerate_data(
+		&self,
+		j: *mut c_void,
+		data: *mut *const u8,
+		length: *mut size_t,
+	) -> c_int {
+		(self.sd_journal_enumerate_data)(j, data, length)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_realtime_usec(&self, j: *mut c_void, ret: *mut u64) -> c_int {
+		(self.sd_journal_get_realtime_usec)(j, ret)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_monotonic_usec(
+		&self,
+		j: *mut c_void,
+		ret: *mut u64,
+		ret_boot_id: *mut sd_id128_t,
+	) -> c_int {
+		(self.sd_journal_get_monotonic_usec)(j, ret, ret_boot_id)
+	}
+
+	pub(crate) unsafe fn sd_journal_wait(&self, j: *mut c_void, timeout_usec: u64) -> c_int {
+		(self.sd_journal_wait)(j, timeout_usec)
+	}
+
+	pub(crate) unsafe fn sd_journal_add_match(
+		&self,
+		j: *mut c_void,
+		data: *const c_void,
+		size: size_t,
+	) -> c_int {
+		(self.sd_journal_add_match)(j, data, size)
+	}
+
+	pub(crate) unsafe fn sd_journal_add_disjunction(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_add_disjunction)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_add_conjunction(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_add_conjunction)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_flush_matches(&self, j: *mut c_void) {
+		(self.sd_journal_flush_matches)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_query_unique(
+		&self,
+		j: *mut c_void,
+		field: *const c_char,
+	) -> c_int {
+		(self.sd_journal_query_unique)(j, field)
+	}
+
+	pub(crate) unsafe fn sd_journal_restart_unique(&self, j: *mut c_void) {
+		(self.sd_journal_restart_unique)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_enumerate_unique(
+		&self,
+		j: *mut c_void,
+		data: *mut *const u8,
+		length: *mut size_t,
+	) -> c_int {
+		(self.sd_journal_enumerate_unique)(j, data, length)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_fd(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_get_fd)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_events(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_get_events)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_timeout(
+		&self,
+		j: *mut c_void,
+		timeout_usec: *mut u64,
+	) -> c_int {
+		(self.sd_journal_get_timeout)(j, timeout_usec)
+	}
+
+	pub(crate) unsafe fn sd_journal_process(&self, j: *mut c_void) -> c_int {
+		(self.sd_journal_process)(j)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_cutoff_realtime_usec(
+		&self,
+		j: *mut c_void,
+		from: *mut u64,
+		to: *mut u64,
+	) -> c_int {
+		(self.sd_journal_get_cutoff_realtime_usec)(j, from, to)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_cutoff_monotonic_usec(
+		&self,
+		j: *mut c_void,
+		boot_id: sd_id128_t,
+		from: *mut u64,
+		to: *mut u64,
+	) -> c_int {
+		(self.sd_journal_get_cutoff_monotonic_usec)(j, boot_id, from, to)
+	}
+
+	pub(crate) unsafe fn sd_journal_next_skip(&self, j: *mut c_void, n: u64) -> c_int {
+		(self.sd_journal_next_skip)(j, n)
+	}
+
+	pub(crate) unsafe fn sd_journal_previous_skip(&self, j: *mut c_void, n: u64) -> c_int {
+		(self.sd_journal_previous_skip)(j, n)
+	}
+
+	pub(crate) unsafe fn sd_journal_get_catalog(
+		&self,
+		j: *mut c_void,
+		text: *mut *mut c_char,
+	) -> c_int {
+		(self.sd_journal_get_catalog)(j, text)
+	}
+
+	pub(crate) unsafe fn sd_journal_sendv(&self, iv: *const const_iovec, n: c_int) -> c_int {
+		(self.sd_journal_sendv)(iv, n)
+	}
+
+	pub(crate) unsafe fn sd_id128_get_boot(&self, ret: *mut sd_id128_t) -> c_int {
+		(self.sd_id128_get_boot)(ret)
+	}
+}
+
+static API: OnceLock<Result<SystemdApi, String>> = OnceLock::new();
+
+/// Resolve (and cache) the `libsystemd` API for the `open` feature.
+///
+/// Returns an `io::Error` of kind `Unsupported` if `libsystemd.so.0` or one
+/// of the symbols this crate needs cannot be found, rather than failing the
+/// whole process at link time.
+pub(crate) fn open_systemd() -> io::Result<&'static SystemdApi> {
+	match API.get_or_init(|| SystemdApi::load().map_err(|e| e.to_string())) {
+		Ok(api) => Ok(api),
+		Err(msg) => Err(io::Error::new(io::ErrorKind::Unsupported, msg.clone())),
+	}
+}
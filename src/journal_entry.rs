@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
 use std::str;
 
 #[cfg(feature = "serde")]
@@ -7,6 +8,12 @@ use serde::{Deserialize, Serialize};
 
 type JournalEntryFields = BTreeMap<String, Vec<u8>>;
 
+/// Upper bound on a single binary field's declared length in
+/// [`JournalEntry::read_export`], so a corrupted or malicious length
+/// prefix can't drive an unbounded allocation before the data backing it
+/// has even been read off the wire.
+const MAX_EXPORT_FIELD_LEN: u64 = 64 * 1024 * 1024;
+
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct JournalEntry {
@@ -81,6 +88,107 @@ impl JournalEntry {
 			.and_then(|v| v.parse::<i64>().ok())
 			.map(|v| JournalEntryTimestamp { timestamp_us: v })
 	}
+
+	/// Serialize this entry to the systemd Journal Export Format
+	/// (`man systemd.journal-fields`, "Journal Export Format").
+	///
+	/// Fields whose value is valid UTF-8 and contains no newline are written
+	/// as `FIELD=value\n`; all other fields (binary or containing a
+	/// newline) are written as `FIELD\n`, followed by the value's length as
+	/// a little-endian `u64`, the raw bytes, and a trailing `\n`. The entry
+	/// is terminated by a blank line.
+	pub fn write_export<W: Write>(&self, w: &mut W) -> io::Result<()> {
+		for (key, value) in &self.fields {
+			let as_text = str::from_utf8(value).ok().filter(|v| !v.contains('\n'));
+
+			match as_text {
+				Some(text) => {
+					writeln!(w, "{}={}", key, text)?;
+				}
+				None => {
+					writeln!(w, "{}", key)?;
+					w.write_all(&(value.len() as u64).to_le_bytes())?;
+					w.write_all(value)?;
+					w.write_all(b"\n")?;
+				}
+			}
+		}
+
+		w.write_all(b"\n")?;
+		Ok(())
+	}
+
+	/// Parse one entry in the systemd Journal Export Format written by
+	/// [`write_export`](Self::write_export), reading up to and including the
+	/// blank line that terminates it.
+	///
+	/// Returns `Ok(None)` if the reader is at EOF before any field is read.
+	pub fn read_export<R: BufRead>(r: &mut R) -> io::Result<Option<Self>> {
+		let mut fields = JournalEntryFields::new();
+
+		loop {
+			let mut line = Vec::new();
+			if r.read_until(b'\n', &mut line)? == 0 {
+				// EOF: treat a clean end-of-stream with no pending fields as
+				// "no more entries", otherwise the stream was truncated.
+				return if fields.is_empty() {
+					Ok(None)
+				} else {
+					Err(io::Error::new(
+						io::ErrorKind::UnexpectedEof,
+						"truncated journal export entry",
+					))
+				};
+			}
+
+			if line == b"\n" {
+				return Ok(Some(JournalEntry { fields }));
+			}
+
+			if line.last() == Some(&b'\n') {
+				line.pop();
+			}
+
+			match memchr::memchr(b'=', &line) {
+				Some(sep) => {
+					let name = String::from_utf8_lossy(&line[..sep]).into_owned();
+					let value = line[sep + 1..].to_vec();
+					fields.insert(name, value);
+				}
+				None => {
+					let name = String::from_utf8_lossy(&line).into_owned();
+
+					let mut len_bytes = [0u8; 8];
+					r.read_exact(&mut len_bytes)?;
+					let len = u64::from_le_bytes(len_bytes);
+
+					if len > MAX_EXPORT_FIELD_LEN {
+						return Err(io::Error::new(
+							io::ErrorKind::InvalidData,
+							format!(
+								"binary journal export field length {} exceeds the {} byte limit",
+								len, MAX_EXPORT_FIELD_LEN
+							),
+						));
+					}
+
+					let mut value = vec![0u8; len as usize];
+					r.read_exact(&mut value)?;
+
+					let mut trailing_newline = [0u8; 1];
+					r.read_exact(&mut trailing_newline)?;
+					if trailing_newline != [b'\n'] {
+						return Err(io::Error::new(
+							io::ErrorKind::InvalidData,
+							"missing newline after binary journal export field",
+						));
+					}
+
+					fields.insert(name, value);
+				}
+			}
+		}
+	}
 }
 
 impl From<&JournalEntryFields> for JournalEntry {
@@ -90,3 +198,63 @@ impl From<&JournalEntryFields> for JournalEntry {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Cursor;
+
+	#[test]
+	fn round_trips_text_fields() {
+		let mut entry = JournalEntry::new();
+		entry
+			.fields
+			.insert("MESSAGE".to_string(), b"hello world".to_vec());
+		entry.fields.insert("PRIORITY".to_string(), b"6".to_vec());
+
+		let mut buf = Vec::new();
+		entry.write_export(&mut buf).unwrap();
+
+		let mut cursor = Cursor::new(buf);
+		let parsed = JournalEntry::read_export(&mut cursor).unwrap().unwrap();
+		assert_eq!(parsed.fields, entry.fields);
+	}
+
+	#[test]
+	fn round_trips_binary_fields() {
+		let mut entry = JournalEntry::new();
+		entry
+			.fields
+			.insert("COREDUMP".to_string(), vec![0u8, 1, 2, b'\n', 255]);
+
+		let mut buf = Vec::new();
+		entry.write_export(&mut buf).unwrap();
+
+		let mut cursor = Cursor::new(buf);
+		let parsed = JournalEntry::read_export(&mut cursor).unwrap().unwrap();
+		assert_eq!(parsed.fields, entry.fields);
+	}
+
+	#[test]
+	fn read_export_returns_none_at_clean_eof() {
+		let mut cursor = Cursor::new(Vec::new());
+		assert!(JournalEntry::read_export(&mut cursor).unwrap().is_none());
+	}
+
+	#[test]
+	fn read_export_rejects_truncated_entry() {
+		let mut cursor = Cursor::new(b"MESSAGE=hi\n".to_vec());
+		let err = JournalEntry::read_export(&mut cursor).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+
+	#[test]
+	fn read_export_rejects_oversized_binary_length() {
+		let mut data = b"COREDUMP\n".to_vec();
+		data.extend_from_slice(&(MAX_EXPORT_FIELD_LEN + 1).to_le_bytes());
+		let mut cursor = Cursor::new(data);
+
+		let err = JournalEntry::read_export(&mut cursor).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	}
+}
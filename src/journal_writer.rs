@@ -1,8 +1,13 @@
-use libc::c_int;
-use libsystemd_sys::{c_void, const_iovec, journal as ffi, size_t};
+use libc::{c_int, c_void, size_t};
+#[cfg(feature = "libsystemd-sys")]
+use libsystemd_sys::journal as ffi;
+#[cfg(feature = "log")]
+use log::{self, Level, Log, Record, SetLoggerError};
+#[cfg(feature = "log")]
+use std::result;
 
 use super::{JournalEntry, Result};
-use crate::ffi_result;
+use crate::{const_iovec, ffi_result};
 
 pub fn submit(entry: &JournalEntry) -> Result<()> {
 	let mut fields = Vec::<Vec<u8>>::new();
@@ -17,6 +22,7 @@ pub fn submit(entry: &JournalEntry) -> Result<()> {
 	let fields_iovec =
 		array_to_iovecs(&fields.iter().map(|v| v.as_slice()).collect::<Vec<&[u8]>>());
 
+	#[cfg(feature = "libsystemd-sys")]
 	unsafe {
 		ffi_result(ffi::sd_journal_sendv(
 			fields_iovec.as_ptr(),
@@ -24,6 +30,14 @@ pub fn submit(entry: &JournalEntry) -> Result<()> {
 		))?
 	};
 
+	#[cfg(feature = "open")]
+	unsafe {
+		ffi_result(super::open_systemd()?.sd_journal_sendv(
+			fields_iovec.as_ptr(),
+			fields_iovec.len() as c_int,
+		))?
+	};
+
 	Ok(())
 }
 
@@ -35,3 +49,197 @@ pub fn array_to_iovecs(args: &[&[u8]]) -> Vec<const_iovec> {
 		})
 		.collect()
 }
+
+/// Send preformatted `KEY=value` fields to systemd-journald
+/// (`sd_journal_sendv`).
+///
+/// This is a relatively low-level operation and probably not suitable
+/// unless you need precise control over which fields are sent; see
+/// [`submit`] for sending a [`JournalEntry`], or [`print`] for a plain
+/// message.
+pub fn send_fields(fields: &[&str]) -> Result<()> {
+	let byte_fields = fields.iter().map(|f| f.as_bytes()).collect::<Vec<&[u8]>>();
+	let iovecs = array_to_iovecs(&byte_fields);
+
+	#[cfg(feature = "libsystemd-sys")]
+	ffi_result(unsafe { ffi::sd_journal_sendv(iovecs.as_ptr(), iovecs.len() as c_int) })?;
+
+	#[cfg(feature = "open")]
+	ffi_result(unsafe {
+		super::open_systemd()?.sd_journal_sendv(iovecs.as_ptr(), iovecs.len() as c_int)
+	})?;
+
+	Ok(())
+}
+
+/// Syslog priority levels, as used by the journal's `PRIORITY` field.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Priority {
+	Emergency = 0,
+	Alert = 1,
+	Critical = 2,
+	Error = 3,
+	Warning = 4,
+	Notice = 5,
+	Info = 6,
+	Debug = 7,
+}
+
+/// Send a simple message to systemd-journald.
+pub fn print(priority: Priority, message: &str) -> Result<()> {
+	send_fields(&[
+		&format!("PRIORITY={}", priority as u8),
+		&format!("MESSAGE={}", message),
+	])
+}
+
+#[cfg(feature = "log")]
+enum SyslogLevel {
+	// Emerg = 0,
+	// Alert = 1,
+	// Crit = 2,
+	Err = 3,
+	Warning = 4,
+	// Notice = 5,
+	Info = 6,
+	Debug = 7,
+}
+
+/// Turn an arbitrary `log::kv` key into a valid journal field name: upper
+/// case letters/digits/underscore only, prefixed if it doesn't start with a
+/// letter.
+#[cfg(feature = "log")]
+fn sanitize_kv_key(key: &str) -> String {
+	let mut out = String::with_capacity(key.len());
+
+	for c in key.chars() {
+		if c.is_ascii_alphanumeric() || c == '_' {
+			out.push(c.to_ascii_uppercase());
+		} else {
+			out.push('_');
+		}
+	}
+
+	if out.chars().next().map_or(true, |c| !c.is_ascii_alphabetic()) {
+		out.insert_str(0, "KV_");
+	}
+
+	out
+}
+
+/// Collects a `log::Record`'s structured key-values as `KEY=value` strings.
+#[cfg(feature = "log")]
+struct KeyValueCollector(Vec<String>);
+
+#[cfg(feature = "log")]
+impl<'kvs> log::kv::Visitor<'kvs> for KeyValueCollector {
+	fn visit_pair(
+		&mut self,
+		key: log::kv::Key<'kvs>,
+		value: log::kv::Value<'kvs>,
+	) -> result::Result<(), log::kv::Error> {
+		self.0
+			.push(format!("{}={}", sanitize_kv_key(key.as_str()), value));
+		Ok(())
+	}
+}
+
+/// Send a `log::Record` to systemd-journald, including its structured
+/// key-values (`record.key_values()`) as first-class journal fields.
+#[cfg(feature = "log")]
+pub fn log_record(record: &Record) {
+	let lvl = match record.level() {
+		Level::Error => SyslogLevel::Err,
+		Level::Warn => SyslogLevel::Warning,
+		Level::Info => SyslogLevel::Info,
+		Level::Debug | Level::Trace => SyslogLevel::Debug,
+	} as usize;
+
+	let mut keys = vec![
+		format!("PRIORITY={}", lvl),
+		format!("MESSAGE={}", record.args()),
+		format!("TARGET={}", record.target()),
+	];
+
+	if let Some(line) = record.line() {
+		keys.push(format!("CODE_LINE={}", line));
+	}
+	if let Some(file) = record.file() {
+		keys.push(format!("CODE_FILE={}", file));
+	}
+	if let Some(module_path) = record.module_path() {
+		keys.push(format!("CODE_FUNCTION={}", module_path));
+	}
+
+	let mut kv_collector = KeyValueCollector(Vec::new());
+	let _ = record.key_values().visit(&mut kv_collector);
+	keys.extend(kv_collector.0);
+
+	let str_keys = keys.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	let _ = send_fields(&str_keys);
+}
+
+/// Logger implementation over systemd-journald.
+#[cfg(feature = "log")]
+pub struct JournalLog;
+
+#[cfg(feature = "log")]
+impl Log for JournalLog {
+	fn enabled(&self, _metadata: &log::Metadata) -> bool {
+		true
+	}
+
+	fn log(&self, record: &Record) {
+		log_record(record);
+	}
+
+	fn flush(&self) {
+		// There is no flushing required.
+	}
+}
+
+#[cfg(feature = "log")]
+static LOGGER: JournalLog = JournalLog;
+
+#[cfg(feature = "log")]
+impl JournalLog {
+	pub fn init() -> result::Result<(), SetLoggerError> {
+		log::set_logger(&LOGGER)
+	}
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sanitize_kv_key_uppercases_and_replaces_invalid_chars() {
+		assert_eq!(sanitize_kv_key("user_id"), "USER_ID");
+		assert_eq!(sanitize_kv_key("request-path"), "REQUEST_PATH");
+		assert_eq!(sanitize_kv_key("a.b.c"), "A_B_C");
+	}
+
+	#[test]
+	fn sanitize_kv_key_prefixes_keys_not_starting_with_a_letter() {
+		assert_eq!(sanitize_kv_key("123abc"), "KV_123ABC");
+		assert_eq!(sanitize_kv_key("_leading"), "KV__LEADING");
+	}
+
+	#[test]
+	fn key_value_collector_sanitizes_and_formats_pairs() {
+		let mut collector = KeyValueCollector(Vec::new());
+
+		collector
+			.visit_pair(log::kv::Key::from("user-id"), log::kv::Value::from(42))
+			.unwrap();
+		collector
+			.visit_pair(log::kv::Key::from("path"), log::kv::Value::from("/tmp"))
+			.unwrap();
+
+		assert_eq!(
+			collector.0,
+			vec!["USER_ID=42".to_string(), "PATH=/tmp".to_string()]
+		);
+	}
+}
@@ -1,10 +1,11 @@
 pub use std::io::{Error, Result};
 use std::mem::MaybeUninit;
 
+#[cfg(feature = "libsystemd-sys")]
 use libsystemd_sys as ffi;
 
 /// Convert a systemd ffi return value into a Result
-pub fn ffi_result(ret: ffi::c_int) -> Result<ffi::c_int> {
+pub fn ffi_result(ret: libc::c_int) -> Result<libc::c_int> {
 	if ret < 0 {
 		Err(Error::from_raw_os_error(-ret))
 	} else {
@@ -36,13 +37,58 @@ pub mod reader;
 #[path = "journal_writer.rs"]
 pub mod writer;
 
-pub struct Id(pub(crate) libsystemd_sys::id128::sd_id128_t);
+#[cfg(feature = "open")]
+mod dlopen;
+#[cfg(feature = "open")]
+pub(crate) use self::dlopen::open_systemd;
+
+/// `struct iovec` layout expected by `sd_journal_sendv`.
+///
+/// Re-exported from `libsystemd-sys` when it's linked in, so the type is
+/// identical either way; otherwise duplicated here so that builds with only
+/// the `open` feature never need to name the `libsystemd-sys` crate.
+#[cfg(feature = "libsystemd-sys")]
+pub(crate) use libsystemd_sys::const_iovec;
+
+#[cfg(not(feature = "libsystemd-sys"))]
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct const_iovec {
+	pub iov_base: *const libc::c_void,
+	pub iov_len: libc::size_t,
+}
+
+/// `sd_id128_t` layout, re-exported from `libsystemd-sys` when it's linked
+/// in; otherwise duplicated here so that builds with only the `open`
+/// feature never need to name the `libsystemd-sys` crate.
+#[cfg(feature = "libsystemd-sys")]
+pub(crate) use libsystemd_sys::id128::sd_id128_t;
+
+#[cfg(not(feature = "libsystemd-sys"))]
+#[repr(C, align(8))]
+#[derive(Clone, Copy)]
+pub(crate) struct sd_id128_t(pub [u8; 16]);
+
+pub struct Id(pub(crate) sd_id128_t);
+
+/// Alias for [`Id`], matching the `sd_id128_t`/`Id128` naming used by other
+/// systemd journal bindings.
+pub type Id128 = Id;
 
 impl Id {
 	pub fn get_boot_id() -> Result<Self> {
 		let mut id = MaybeUninit::uninit();
 
-		unsafe { ffi_result(ffi::id128::sd_id128_get_boot(id.as_mut_ptr())) }?;
+		#[cfg(feature = "libsystemd-sys")]
+		unsafe {
+			ffi_result(ffi::id128::sd_id128_get_boot(id.as_mut_ptr()))
+		}?;
+
+		#[cfg(feature = "open")]
+		unsafe {
+			ffi_result(open_systemd()?.sd_id128_get_boot(id.as_mut_ptr()))
+		}?;
+
 		Ok(Self(unsafe { id.assume_init() }))
 	}
 }
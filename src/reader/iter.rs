@@ -57,16 +57,3 @@ impl<'a> Iterator for JournalBlockingIter<'a> {
 		self.next_wait().transpose()
 	}
 }
-
-		let ret = if ret.is_ok() && ret.as_ref().unwrap().is_none() {
-			if let Err(e) = self.reader.wait() {
-				return Some(Err(e));
-			}
-			self.reader.next_entry()
-		} else {
-			ret
-		};
-
-		ret.transpose()
-	}
-}
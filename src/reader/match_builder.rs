@@ -0,0 +1,55 @@
+use super::JournalReader;
+use crate::Result;
+
+/// Incrementally builds a boolean match expression on a [`JournalReader`],
+/// inserting [`add_conjunction`](JournalReader::add_conjunction) /
+/// [`add_disjunction`](JournalReader::add_disjunction) calls at the right
+/// points so the caller doesn't have to track group boundaries by hand.
+///
+/// Matches added between two calls to [`and`](Self::and)/[`or`](Self::or)
+/// form one group; consecutive groups are combined with whichever of
+/// `and`/`or` closed the previous one. For example, to express
+/// `(PRIORITY=3 OR PRIORITY=4) AND _SYSTEMD_UNIT=foo.service`:
+///
+/// ```no_run
+/// # use journald::reader::{JournalReader, JournalReaderConfig, MatchBuilder};
+/// # fn main() -> std::io::Result<()> {
+/// let mut journal = JournalReader::open(&JournalReaderConfig::default())?;
+/// MatchBuilder::new(&mut journal)
+///     .matches("PRIORITY=3")?
+///     .matches("PRIORITY=4")?
+///     .and()?
+///     .matches("_SYSTEMD_UNIT=foo.service")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MatchBuilder<'a> {
+	reader: &'a mut JournalReader,
+}
+
+impl<'a> MatchBuilder<'a> {
+	/// Start building a match expression on `reader`. Does not clear any
+	/// matches already present; call
+	/// [`flush_matches`](JournalReader::flush_matches) first if needed.
+	pub fn new(reader: &'a mut JournalReader) -> Self {
+		Self { reader }
+	}
+
+	/// Add a match to the current group.
+	pub fn matches(self, filter: &str) -> Result<Self> {
+		self.reader.add_filter(filter)?;
+		Ok(self)
+	}
+
+	/// Close the current group with a logical AND.
+	pub fn and(self) -> Result<Self> {
+		self.reader.add_conjunction()?;
+		Ok(self)
+	}
+
+	/// Close the current group with a logical OR.
+	pub fn or(self) -> Result<Self> {
+		self.reader.add_disjunction()?;
+		Ok(self)
+	}
+}
@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::io::Error;
+#[cfg(feature = "async")]
+use std::os::unix::io::RawFd;
 use std::ptr;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "open")]
 use libc::c_void;
@@ -17,6 +19,16 @@ mod iter;
 #[doc(inline)]
 pub use iter::{JournalBlockingIter, JournalIter};
 
+mod match_builder;
+#[doc(inline)]
+pub use match_builder::MatchBuilder;
+
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "async")]
+#[doc(inline)]
+pub use stream::JournalStream;
+
 // A single log entry from journal.
 
 #[allow(dead_code)]
@@ -89,10 +101,23 @@ pub enum JournalFiles {
 }
 
 /// Seeking position in journal.
+///
+/// Like `Head`/`Tail`/`Cursor`, the timestamp-based variants only position
+/// the read pointer: the caller must still call
+/// [`next_entry`](JournalReader::next_entry) or
+/// [`previous_entry`](JournalReader::previous_entry) afterwards to land on
+/// the first matching entry.
 pub enum JournalSeek {
 	Head,
 	Tail,
 	Cursor(String),
+	/// Seek to the first entry with a wall-clock timestamp at or after the
+	/// given point in time (`sd_journal_seek_realtime_usec`).
+	Realtime(SystemTime),
+	/// Seek to the first entry with a monotonic timestamp (relative to the
+	/// given boot) at or after `usec` microseconds since boot
+	/// (`sd_journal_seek_monotonic_usec`).
+	Monotonic { boot_id: crate::Id128, usec: u64 },
 }
 
 /// Wakeup event types
@@ -250,11 +275,18 @@ impl JournalReader {
 		{
 			unsafe {
 				let b = ::std::slice::from_raw_parts(data, sz as usize);
-				let field = String::from_utf8_lossy(b);
-				let mut name_value = field.splitn(2, '=');
-				let name = name_value.next().unwrap();
-				let value = name_value.next().unwrap();
-				fields.insert(From::from(name), From::from(value));
+
+				// Field values may be binary (e.g. `COREDUMP`) or contain
+				// embedded NULs, so only the name is decoded as UTF-8; the
+				// value is kept as raw bytes rather than going through a
+				// lossy UTF-8 conversion.
+				let sep = match memchr::memchr(b'=', b) {
+					Some(sep) => sep,
+					None => continue,
+				};
+				let name = String::from_utf8_lossy(&b[..sep]).into_owned();
+				let value = b[sep + 1..].to_vec();
+				fields.insert(name, value);
 			}
 		}
 
@@ -271,7 +303,7 @@ impl JournalReader {
 
 		fields.insert(
 			"__REALTIME_TIMESTAMP".to_string(),
-			timestamp_realtime_us.to_string(),
+			timestamp_realtime_us.to_string().into_bytes(),
 		);
 
 		let mut timestamp_monotonic_us: u64 = 0;
@@ -287,7 +319,7 @@ impl JournalReader {
 
 		fields.insert(
 			"__MONOTONIC_TIMESTAMP".to_string(),
-			timestamp_monotonic_us.to_string(),
+			timestamp_monotonic_us.to_string().into_bytes(),
 		);
 
 		let cursor;
@@ -304,7 +336,7 @@ impl JournalReader {
 
 		fields.insert(
 			"__CURSOR".to_string(),
-			cursor.to_string_lossy().into_owned(),
+			cursor.to_string_lossy().into_owned().into_bytes(),
 		);
 
 		unsafe {
@@ -373,6 +405,29 @@ impl JournalReader {
 				#[cfg(feature = "open")]
 				ffi_result(super::open_systemd()?.sd_journal_seek_cursor(self.j, cur))?;
 			},
+			JournalSeek::Realtime(time) => {
+				let usec = duration_to_usec(
+					time.duration_since(UNIX_EPOCH)
+						.map_err(|_| Error::from_raw_os_error(libc::EINVAL))?,
+				)?;
+
+				#[cfg(feature = "libsystemd-sys")]
+				ffi_result(unsafe { ffi::sd_journal_seek_realtime_usec(self.j, usec) })?;
+
+				#[cfg(feature = "open")]
+				ffi_result(unsafe {
+					super::open_systemd()?.sd_journal_seek_realtime_usec(self.j, usec)
+				})?;
+			}
+			JournalSeek::Monotonic { boot_id, usec } => {
+				#[cfg(feature = "libsystemd-sys")]
+				ffi_result(unsafe { ffi::sd_journal_seek_monotonic_usec(self.j, boot_id.0, usec) })?;
+
+				#[cfg(feature = "open")]
+				ffi_result(unsafe {
+					super::open_systemd()?.sd_journal_seek_monotonic_usec(self.j, boot_id.0, usec)
+				})?;
+			}
 		};
 
 		Ok(())
@@ -421,6 +476,242 @@ impl JournalReader {
 		Ok(())
 	}
 
+	/// Insert a disjunction (logical OR) between the matches added so far
+	/// and any matches added after this call (`sd_journal_add_disjunction`).
+	///
+	/// Matches added via [`add_filter`](Self::add_filter) for the same
+	/// field are OR-ed together automatically, and matches for different
+	/// fields are AND-ed together; `add_disjunction`/`add_conjunction`
+	/// let you close one of those groups explicitly, e.g. to express
+	/// `(PRIORITY=3 OR PRIORITY=4) AND _SYSTEMD_UNIT=foo.service`.
+	pub fn add_disjunction(&mut self) -> Result<()> {
+		ffi_result(unsafe {
+			#[cfg(feature = "libsystemd-sys")]
+			{
+				ffi::sd_journal_add_disjunction(self.j)
+			}
+
+			#[cfg(feature = "open")]
+			{
+				super::open_systemd()?.sd_journal_add_disjunction(self.j)
+			}
+		})?;
+
+		Ok(())
+	}
+
+	/// Insert a conjunction (logical AND) between the matches added so far
+	/// and any matches added after this call (`sd_journal_add_conjunction`).
+	pub fn add_conjunction(&mut self) -> Result<()> {
+		ffi_result(unsafe {
+			#[cfg(feature = "libsystemd-sys")]
+			{
+				ffi::sd_journal_add_conjunction(self.j)
+			}
+
+			#[cfg(feature = "open")]
+			{
+				super::open_systemd()?.sd_journal_add_conjunction(self.j)
+			}
+		})?;
+
+		Ok(())
+	}
+
+	/// Clear all matches added via [`add_filter`](Self::add_filter),
+	/// [`add_disjunction`](Self::add_disjunction) and
+	/// [`add_conjunction`](Self::add_conjunction), so the reader can be
+	/// reused for a new query (`sd_journal_flush_matches`).
+	pub fn flush_matches(&mut self) -> Result<()> {
+		#[cfg(feature = "libsystemd-sys")]
+		unsafe {
+			ffi::sd_journal_flush_matches(self.j)
+		};
+
+		#[cfg(feature = "open")]
+		unsafe {
+			super::open_systemd()?.sd_journal_flush_matches(self.j)
+		};
+
+		Ok(())
+	}
+
+	/// Return all distinct raw values a field takes across all entries in
+	/// the open journal files (e.g. `_SYSTEMD_UNIT`, `_HOSTNAME`), built
+	/// from `sd_journal_query_unique` and `sd_journal_enumerate_unique`.
+	/// Matches added with [`add_filter`](Self::add_filter) are not applied.
+	///
+	/// This is the building block for populating filter drop-downs; use
+	/// [`add_filter`](Self::add_filter) to actually narrow entries down to
+	/// one of the returned values.
+	pub fn query_unique(&mut self, field: &str) -> Result<Vec<Vec<u8>>> {
+		let field = std::ffi::CString::new(field)?;
+
+		#[cfg(feature = "libsystemd-sys")]
+		ffi_result(unsafe { ffi::sd_journal_query_unique(self.j, field.as_ptr()) })?;
+		#[cfg(feature = "open")]
+		let api = super::open_systemd()?;
+		#[cfg(feature = "open")]
+		ffi_result(unsafe { api.sd_journal_query_unique(self.j, field.as_ptr()) })?;
+
+		#[cfg(feature = "libsystemd-sys")]
+		unsafe {
+			ffi::sd_journal_restart_unique(self.j)
+		};
+		#[cfg(feature = "open")]
+		unsafe {
+			api.sd_journal_restart_unique(self.j)
+		};
+
+		let mut values = Vec::new();
+		let mut sz: size_t = 0;
+		let mut data: *const u8 = ptr::null();
+
+		while unsafe {
+			#[cfg(feature = "libsystemd-sys")]
+			{
+				ffi::sd_journal_enumerate_unique(self.j, &mut data as *mut *const u8, &mut sz)
+			}
+
+			#[cfg(feature = "open")]
+			{
+				api.sd_journal_enumerate_unique(self.j, &mut data as *mut *const u8, &mut sz)
+			}
+		} > 0
+		{
+			unsafe {
+				let b = ::std::slice::from_raw_parts(data, sz as usize);
+				let value = match memchr::memchr(b'=', b) {
+					Some(sep) => b[sep + 1..].to_vec(),
+					None => b.to_vec(),
+				};
+				values.push(value);
+			}
+		}
+
+		Ok(values)
+	}
+
+	/// Convenience wrapper over [`query_unique`](Self::query_unique) for
+	/// fields whose values are always text, lossily decoding each value as
+	/// UTF-8 (the analogue of go-systemd's `GetUniqueValues`). Use
+	/// `query_unique` directly for fields that may hold binary data.
+	pub fn unique_values(&mut self, field: &str) -> Result<Vec<String>> {
+		Ok(self
+			.query_unique(field)?
+			.into_iter()
+			.map(|v| String::from_utf8_lossy(&v).into_owned())
+			.collect())
+	}
+
+	/// Return the oldest and newest wall-clock timestamps of any entry
+	/// currently stored in the open journal files
+	/// (`sd_journal_get_cutoff_realtime_usec`), or `None` if the journal
+	/// has no entries.
+	pub fn cutoff_realtime(&mut self) -> Result<Option<(Duration, Duration)>> {
+		let mut from: u64 = 0;
+		let mut to: u64 = 0;
+
+		#[cfg(feature = "libsystemd-sys")]
+		let ret = ffi_result(unsafe {
+			ffi::sd_journal_get_cutoff_realtime_usec(self.j, &mut from, &mut to)
+		})?;
+
+		#[cfg(feature = "open")]
+		let ret = ffi_result(unsafe {
+			super::open_systemd()?.sd_journal_get_cutoff_realtime_usec(self.j, &mut from, &mut to)
+		})?;
+
+		if ret == 0 {
+			Ok(None)
+		} else {
+			Ok(Some((Duration::from_micros(from), Duration::from_micros(to))))
+		}
+	}
+
+	/// Return the oldest and newest monotonic timestamps (microseconds
+	/// since `boot_id`) of any entry from that boot currently stored in the
+	/// open journal files (`sd_journal_get_cutoff_monotonic_usec`), or
+	/// `None` if there are no entries from that boot.
+	pub fn cutoff_monotonic(&mut self, boot_id: crate::Id128) -> Result<Option<(u64, u64)>> {
+		let mut from: u64 = 0;
+		let mut to: u64 = 0;
+
+		#[cfg(feature = "libsystemd-sys")]
+		let ret = ffi_result(unsafe {
+			ffi::sd_journal_get_cutoff_monotonic_usec(self.j, boot_id.0, &mut from, &mut to)
+		})?;
+
+		#[cfg(feature = "open")]
+		let ret = ffi_result(unsafe {
+			super::open_systemd()?.sd_journal_get_cutoff_monotonic_usec(
+				self.j, boot_id.0, &mut from, &mut to,
+			)
+		})?;
+
+		if ret == 0 {
+			Ok(None)
+		} else {
+			Ok(Some((from, to)))
+		}
+	}
+
+	/// Advance `n` entries forward at once (`sd_journal_next_skip`),
+	/// returning the number of entries actually advanced, which may be
+	/// less than `n` if the end of the journal was reached.
+	pub fn next_skip(&mut self, n: u64) -> Result<u64> {
+		#[cfg(feature = "libsystemd-sys")]
+		let advanced = ffi_result(unsafe { ffi::sd_journal_next_skip(self.j, n) })?;
+
+		#[cfg(feature = "open")]
+		let advanced = ffi_result(unsafe { super::open_systemd()?.sd_journal_next_skip(self.j, n) })?;
+
+		Ok(advanced as u64)
+	}
+
+	/// Move `n` entries backward at once (`sd_journal_previous_skip`),
+	/// returning the number of entries actually advanced, which may be
+	/// less than `n` if the start of the journal was reached.
+	pub fn previous_skip(&mut self, n: u64) -> Result<u64> {
+		#[cfg(feature = "libsystemd-sys")]
+		let advanced = ffi_result(unsafe { ffi::sd_journal_previous_skip(self.j, n) })?;
+
+		#[cfg(feature = "open")]
+		let advanced =
+			ffi_result(unsafe { super::open_systemd()?.sd_journal_previous_skip(self.j, n) })?;
+
+		Ok(advanced as u64)
+	}
+
+	/// Return the message catalog text associated with the current entry's
+	/// `MESSAGE_ID` (`sd_journal_get_catalog`), i.e. the explanatory
+	/// documentation `journalctl -x` shows, or `None` if there is no
+	/// catalog entry for it.
+	pub fn catalog(&mut self) -> Result<Option<String>> {
+		let mut text: *mut c_char = ptr::null_mut();
+
+		#[cfg(feature = "libsystemd-sys")]
+		let ret = unsafe { ffi::sd_journal_get_catalog(self.j, &mut text) };
+
+		#[cfg(feature = "open")]
+		let ret = unsafe { super::open_systemd()?.sd_journal_get_catalog(self.j, &mut text) };
+
+		if ret == -libc::ENOENT {
+			return Ok(None);
+		}
+		ffi_result(ret)?;
+
+		let catalog = unsafe { ::std::ffi::CStr::from_ptr(text) }
+			.to_string_lossy()
+			.into_owned();
+
+		unsafe {
+			free(text as *mut ::libc::c_void);
+		}
+
+		Ok(Some(catalog))
+	}
+
 	/// Create a blocking Iterator from the reader.
 	pub fn as_blocking_iter(&mut self) -> JournalBlockingIter {
 		JournalBlockingIter {
@@ -438,6 +729,82 @@ impl JournalReader {
 	pub fn as_iter(&mut self) -> JournalIter {
 		JournalIter { reader: self }
 	}
+
+	/// Turn this reader into an async [`futures::Stream`] of journal
+	/// entries, driven by the journal's wakeup file descriptor rather than
+	/// a dedicated blocking thread.
+	///
+	/// Requires an async reactor (currently tokio) to poll the returned
+	/// stream; see [`JournalStream`] for the details of how wakeups are
+	/// translated into entries.
+	#[cfg(feature = "async")]
+	pub fn as_stream(&mut self) -> Result<JournalStream> {
+		JournalStream::new(self)
+	}
+
+	/// Return the journal's wakeup file descriptor (`sd_journal_get_fd`).
+	///
+	/// The fd becomes readable when the journal files may have changed;
+	/// [`process`](Self::process) then reports what happened, which can be
+	/// `NOP`. It is intended to be registered with a custom async reactor,
+	/// not read from directly.
+	#[cfg(feature = "async")]
+	pub fn raw_fd(&self) -> Result<RawFd> {
+		#[cfg(feature = "libsystemd-sys")]
+		return ffi_result(unsafe { ffi::sd_journal_get_fd(self.j) });
+
+		#[cfg(feature = "open")]
+		return ffi_result(unsafe { super::open_systemd()?.sd_journal_get_fd(self.j) });
+	}
+
+	/// Return the poll event mask (e.g. `POLLIN`) the caller should
+	/// register interest in for the journal's fd (`sd_journal_get_events`).
+	#[cfg(feature = "async")]
+	pub fn get_events(&self) -> Result<c_int> {
+		#[cfg(feature = "libsystemd-sys")]
+		return ffi_result(unsafe { ffi::sd_journal_get_events(self.j) });
+
+		#[cfg(feature = "open")]
+		return ffi_result(unsafe { super::open_systemd()?.sd_journal_get_events(self.j) });
+	}
+
+	/// Return the deadline by which [`process`](Self::process) must be
+	/// called even without fd activity (`sd_journal_get_timeout`), or
+	/// `None` if there is currently no such deadline.
+	#[cfg(feature = "async")]
+	pub fn get_timeout(&self) -> Result<Option<Duration>> {
+		let mut usec: u64 = 0;
+
+		#[cfg(feature = "libsystemd-sys")]
+		ffi_result(unsafe { ffi::sd_journal_get_timeout(self.j, &mut usec) })?;
+
+		#[cfg(feature = "open")]
+		ffi_result(unsafe { super::open_systemd()?.sd_journal_get_timeout(self.j, &mut usec) })?;
+
+		if usec == u64::MAX {
+			Ok(None)
+		} else {
+			Ok(Some(Duration::from_micros(usec)))
+		}
+	}
+
+	/// Process a wakeup of the journal's fd (`sd_journal_process`), returning
+	/// why it fired. Must be called after every readiness notification
+	/// before new events will be delivered; [`wait`](Self::wait) does this
+	/// itself. An
+	/// `INVALIDATE` result means journal files were added or removed and any
+	/// cursor-independent position should be re-established with
+	/// [`seek`](Self::seek).
+	#[cfg(feature = "async")]
+	pub fn process(&mut self) -> Result<WakeupType> {
+		#[cfg(feature = "libsystemd-sys")]
+		let ret = ffi_result(unsafe { ffi::sd_journal_process(self.j) })?;
+
+		#[cfg(feature = "open")]
+		let ret = ffi_result(unsafe { super::open_systemd()?.sd_journal_process(self.j) })?;
+
+		WakeupType::try_from(ret)
+	}
 }
 
 impl Drop for JournalReader {
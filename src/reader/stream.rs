@@ -0,0 +1,67 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::unix::AsyncFd;
+
+use super::{JournalReader, WakeupType};
+use crate::JournalEntry;
+
+struct Fd(RawFd);
+
+impl AsRawFd for Fd {
+	fn as_raw_fd(&self) -> RawFd {
+		self.0
+	}
+}
+
+/// A [`Stream`] of journal entries driven by the journal's wakeup file
+/// descriptor, for use with an async reactor (currently tokio) instead of a
+/// dedicated polling thread.
+///
+/// Each time the underlying fd becomes readable, `sd_journal_process` is
+/// called to find out why; on `APPEND`/`INVALIDATE` the reader is drained
+/// via `next_entry()` until it yields `None`, and the fd is awaited again.
+pub struct JournalStream<'a> {
+	reader: &'a mut JournalReader,
+	async_fd: AsyncFd<Fd>,
+}
+
+impl<'a> JournalStream<'a> {
+	pub(crate) fn new(reader: &'a mut JournalReader) -> io::Result<Self> {
+		let fd = reader.raw_fd()?;
+		let async_fd = AsyncFd::new(Fd(fd))?;
+
+		Ok(Self { reader, async_fd })
+	}
+}
+
+impl<'a> Stream for JournalStream<'a> {
+	type Item = io::Result<JournalEntry>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		loop {
+			match this.reader.next_entry() {
+				Ok(Some(entry)) => return Poll::Ready(Some(Ok(entry))),
+				Ok(None) => {}
+				Err(e) => return Poll::Ready(Some(Err(e))),
+			}
+
+			let mut guard = match this.async_fd.poll_read_ready(cx) {
+				Poll::Ready(Ok(guard)) => guard,
+				Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+				Poll::Pending => return Poll::Pending,
+			};
+
+			match this.reader.process() {
+				Ok(WakeupType::NOP) => guard.clear_ready(),
+				Ok(WakeupType::APPEND) | Ok(WakeupType::INVALIDATE) => guard.clear_ready(),
+				Err(e) => return Poll::Ready(Some(Err(e))),
+			}
+		}
+	}
+}
@@ -29,7 +29,7 @@ fn test_reverse_walk() {
 		entry.set_message(message);
 		entry
 			.fields
-			.insert(FILTER_FIELD.to_string(), filter.to_string());
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
 		journald::writer::submit(&entry).expect("journald write failed");
 	}
 
@@ -89,7 +89,7 @@ fn iter_blocking() {
 		entry.set_message(message);
 		entry
 			.fields
-			.insert(FILTER_FIELD.to_string(), filter.to_string());
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
 		journald::writer::submit(&entry).expect("journald write failed");
 	}
 
@@ -128,3 +128,426 @@ fn iter_blocking() {
 		panic!("Did not receive right amount of systemd iter messages");
 	}
 }
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn as_stream_yields_submitted_entries() {
+	use futures_core::Stream;
+	use std::pin::Pin;
+
+	let filter: String = format!("test_as_stream_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let messages_expected = vec!["stream: rust-systemd test 1", "stream: rust-systemd test 2"];
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("Could not set journald filter");
+
+	journal
+		.seek(JournalSeek::Tail)
+		.expect("journal seek failed");
+
+	for message in &messages_expected {
+		let mut entry = JournalEntry::new();
+		entry.set_message(message);
+		entry
+			.fields
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	let mut stream = journal.as_stream().expect("as_stream failed");
+
+	for expected in &messages_expected {
+		let entry = tokio::time::timeout(
+			Duration::from_secs(5),
+			std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)),
+		)
+		.await
+		.expect("timed out waiting for entry")
+		.expect("stream ended early")
+		.expect("stream yielded an error");
+
+		assert_eq!(entry.get_message().unwrap().to_string(), *expected);
+	}
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn custom_reactor_primitives_are_usable() {
+	let filter: String = format!("test_custom_reactor_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("Could not set journald filter");
+
+	let fd = journal.raw_fd().expect("raw_fd failed");
+	assert!(fd >= 0, "expected a valid wakeup fd, got {}", fd);
+
+	journal.get_events().expect("get_events failed");
+	journal.get_timeout().expect("get_timeout failed");
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("custom reactor test");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	// give systemd internals some time to mark the fd ready
+	std::thread::sleep(std::time::Duration::from_millis(200));
+
+	let wakeup = journal.process().expect("process failed");
+	println!("got wakeup {:?}", wakeup);
+}
+
+#[test]
+fn boolean_match_groups_combine_with_conjunction() {
+	let filter: String = format!("test_match_groups_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let messages = vec![("match 1", "a"), ("no match", "b"), ("match 2", "a")];
+
+	for (message, tag) in &messages {
+		let mut entry = JournalEntry::new();
+		entry.set_message(message);
+		entry
+			.fields
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+		entry
+			.fields
+			.insert("RUST_JOURNALD_TAG".to_string(), tag.as_bytes().to_vec());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	// give systemd internals some time
+	std::thread::sleep(std::time::Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+	journal.add_conjunction().expect("add_conjunction failed");
+	journal
+		.add_filter("RUST_JOURNALD_TAG=a")
+		.expect("add_filter failed");
+
+	journal.seek(JournalSeek::Head).expect("journal seek failed");
+
+	let mut seen = Vec::new();
+	while let Some(entry) = journal.next_entry().expect("next_entry failed") {
+		seen.push(entry.get_message().unwrap().to_string());
+	}
+	assert_eq!(seen, vec!["match 1".to_string(), "match 2".to_string()]);
+
+	journal.flush_matches().expect("flush_matches failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+	journal.seek(JournalSeek::Head).expect("journal seek failed");
+
+	let mut seen_after_flush = Vec::new();
+	while let Some(entry) = journal.next_entry().expect("next_entry failed") {
+		seen_after_flush.push(entry.get_message().unwrap().to_string());
+	}
+	assert_eq!(seen_after_flush.len(), messages.len());
+}
+
+#[test]
+fn match_builder_combines_or_and_and_groups() {
+	let filter: String = format!("test_match_builder_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let messages = vec![("p3", "3"), ("p4", "4"), ("p5", "5")];
+
+	for (message, priority) in &messages {
+		let mut entry = JournalEntry::new();
+		entry.set_message(message);
+		entry
+			.fields
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+		entry
+			.fields
+			.insert("RUST_JOURNALD_PRIORITY".to_string(), priority.as_bytes().to_vec());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	// give systemd internals some time
+	std::thread::sleep(std::time::Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	MatchBuilder::new(&mut journal)
+		.matches(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("matches failed")
+		.and()
+		.expect("and failed")
+		.matches("RUST_JOURNALD_PRIORITY=3")
+		.expect("matches failed")
+		.matches("RUST_JOURNALD_PRIORITY=4")
+		.expect("matches failed");
+
+	journal.seek(JournalSeek::Head).expect("journal seek failed");
+
+	let mut seen = Vec::new();
+	while let Some(entry) = journal.next_entry().expect("next_entry failed") {
+		seen.push(entry.get_message().unwrap().to_string());
+	}
+	assert_eq!(seen, vec!["p3".to_string(), "p4".to_string()]);
+}
+
+#[test]
+fn seeking_by_realtime_finds_entries_after_cutoff() {
+	let filter: String = format!("test_seek_realtime_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("seek realtime test 1");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	std::thread::sleep(Duration::from_millis(1500));
+	let cutoff = SystemTime::now();
+	std::thread::sleep(Duration::from_millis(1500));
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("seek realtime test 2");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	std::thread::sleep(Duration::from_secs(1));
+
+	journal
+		.seek(JournalSeek::Realtime(cutoff))
+		.expect("journal seek failed");
+
+	let entry = journal
+		.next_entry()
+		.expect("next_entry failed")
+		.expect("no entry found after cutoff");
+	assert_eq!(
+		entry.get_message().unwrap().to_string(),
+		"seek realtime test 2"
+	);
+}
+
+#[test]
+fn query_unique_returns_distinct_field_values() {
+	let field_name = format!("RUST_JOURNALD_UNIQ_{}", rand::random::<u64>());
+	println!("random field: {}", field_name);
+
+	for tag in &["a", "b", "a"] {
+		let mut entry = JournalEntry::new();
+		entry.set_message("query unique test");
+		entry
+			.fields
+			.insert(field_name.clone(), tag.as_bytes().to_vec());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	// give systemd internals some time
+	std::thread::sleep(Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	let mut values = journal
+		.query_unique(&field_name)
+		.expect("query_unique failed")
+		.into_iter()
+		.map(|v| String::from_utf8(v).unwrap())
+		.collect::<Vec<_>>();
+	values.sort();
+
+	assert_eq!(values, vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn unique_values_wraps_query_unique_as_strings() {
+	let field_name = format!("RUST_JOURNALD_UNIQ_STR_{}", rand::random::<u64>());
+	println!("random field: {}", field_name);
+
+	for tag in &["x", "y"] {
+		let mut entry = JournalEntry::new();
+		entry.set_message("unique values test");
+		entry
+			.fields
+			.insert(field_name.clone(), tag.as_bytes().to_vec());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	// give systemd internals some time
+	std::thread::sleep(Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+
+	let mut values = journal
+		.unique_values(&field_name)
+		.expect("unique_values failed");
+	values.sort();
+
+	assert_eq!(values, vec!["x".to_string(), "y".to_string()]);
+}
+
+#[test]
+fn cutoff_realtime_brackets_submitted_entries() {
+	let filter: String = format!("test_cutoff_realtime_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let before = SystemTime::now();
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("cutoff realtime test");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	std::thread::sleep(Duration::from_secs(1));
+	let after = SystemTime::now();
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+
+	let (from, to) = journal
+		.cutoff_realtime()
+		.expect("cutoff_realtime failed")
+		.expect("expected a cutoff range for a matched entry");
+
+	assert!(from <= to);
+	// The cutoffs cover every entry in the open files, not just the matched
+	// one, so the range must reach into the window around the submission.
+	assert!(from <= after.duration_since(UNIX_EPOCH).unwrap());
+	assert!(to >= before.duration_since(UNIX_EPOCH).unwrap());
+}
+
+#[test]
+fn cutoff_monotonic_brackets_submitted_entries() {
+	let filter: String = format!("test_cutoff_monotonic_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("cutoff monotonic test");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	std::thread::sleep(Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+
+	let boot_id = journald::Id128::get_boot_id().expect("get_boot_id failed");
+
+	let (from, to) = journal
+		.cutoff_monotonic(boot_id)
+		.expect("cutoff_monotonic failed")
+		.expect("expected a cutoff range for a matched entry");
+
+	assert!(from <= to);
+}
+
+#[test]
+fn next_skip_and_previous_skip_advance_by_n_entries() {
+	let filter: String = format!("test_skip_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let messages_expected = vec!["skip test 1", "skip test 2", "skip test 3"];
+
+	for message in &messages_expected {
+		let mut entry = JournalEntry::new();
+		entry.set_message(message);
+		entry
+			.fields
+			.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+		journald::writer::submit(&entry).expect("journald write failed");
+	}
+
+	std::thread::sleep(Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+
+	journal.seek(JournalSeek::Head).expect("journal seek failed");
+
+	let advanced = journal.next_skip(2).expect("next_skip failed");
+	assert_eq!(advanced, 2);
+
+	let entry = journal
+		.next_entry()
+		.expect("next_entry failed")
+		.expect("expected an entry after next_skip");
+	assert_eq!(entry.get_message().unwrap().to_string(), "skip test 3");
+
+	journal.seek(JournalSeek::Tail).expect("journal seek failed");
+
+	let advanced = journal.previous_skip(2).expect("previous_skip failed");
+	assert_eq!(advanced, 2);
+
+	let entry = journal
+		.previous_entry()
+		.expect("previous_entry failed")
+		.expect("expected an entry after previous_skip");
+	assert_eq!(entry.get_message().unwrap().to_string(), "skip test 1");
+}
+
+#[test]
+fn catalog_returns_none_without_a_message_id() {
+	let filter: String = format!("test_catalog_{}", rand::random::<u64>());
+	println!("random filter: {}={}", FILTER_FIELD, filter);
+
+	let mut entry = JournalEntry::new();
+	entry.set_message("catalog test, no MESSAGE_ID set");
+	entry
+		.fields
+		.insert(FILTER_FIELD.to_string(), filter.clone().into_bytes());
+	journald::writer::submit(&entry).expect("journald write failed");
+
+	std::thread::sleep(Duration::from_secs(1));
+
+	let mut journal =
+		JournalReader::open(&JournalReaderConfig::default()).expect("journal open failed");
+	journal
+		.add_filter(&format!("{}={}", FILTER_FIELD, filter))
+		.expect("add_filter failed");
+
+	journal.seek(JournalSeek::Head).expect("journal seek failed");
+	journal
+		.next_entry()
+		.expect("next_entry failed")
+		.expect("expected an entry");
+
+	assert_eq!(journal.catalog().expect("catalog failed"), None);
+}